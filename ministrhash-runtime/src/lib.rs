@@ -0,0 +1,144 @@
+//! Runtime (non-macro) equivalents of the hashes produced by the `str_hash_*`/`bytes_hash_*`
+//! compile-time macros, for hashing data that is only known at runtime (e.g. a string read from a
+//! file) in a way that is guaranteed to agree with a macro-generated constant for the same bytes.
+
+/// Hashes the bytes to a `u32` using the FNV1a algorithm (prime `0x0100_0193`) with the given
+/// `seed`, matching the `fnv1a(key, seed = ...)` construction used internally by the
+/// [`crate`]-level `str_match!` perfect-hash dispatch macro's generated lookup code.
+pub const fn fnv1a_u32_seeded(bytes: &[u8], seed: u32) -> u32 {
+    const FNV1A_PRIME: u32 = 0x0100_0193;
+
+    let mut hash = seed;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u32).wrapping_mul(FNV1A_PRIME);
+        i += 1;
+    }
+
+    hash
+}
+
+/// Hashes the bytes to a `u32` using the same FNV1a algorithm (seed `0x811C_9DC5`, prime
+/// `0x0100_0193`) as the `str_hash_fnv1a!`/`bytes_hash_fnv1a!` macros.
+///
+/// ```
+/// assert_eq!(ministrhash_runtime::fnv1a_u32(b"foo"), ministrhash::str_hash_fnv1a!("foo"));
+/// ```
+pub const fn fnv1a_u32(bytes: &[u8]) -> u32 {
+    const FNV1A_SEED: u32 = 0x811C_9DC5;
+
+    fnv1a_u32_seeded(bytes, FNV1A_SEED)
+}
+
+/// One SipHash round: `SIPROUND` from the reference SipHash algorithm.
+const fn sipround(v0: u64, v1: u64, v2: u64, v3: u64) -> (u64, u64, u64, u64) {
+    let v0 = v0.wrapping_add(v1);
+    let v1 = v1.rotate_left(13) ^ v0;
+    let v0 = v0.rotate_left(32);
+
+    let v2 = v2.wrapping_add(v3);
+    let v3 = v3.rotate_left(16) ^ v2;
+
+    let v0 = v0.wrapping_add(v3);
+    let v3 = v3.rotate_left(21) ^ v0;
+
+    let v2 = v2.wrapping_add(v1);
+    let v1 = v1.rotate_left(17) ^ v2;
+    let v2 = v2.rotate_left(32);
+
+    (v0, v1, v2, v3)
+}
+
+/// Returns the byte at index `i` of the logical message `bytes` followed by `trailer` (if any),
+/// i.e. `i == bytes.len()` yields `trailer.unwrap()`. Lets the SipHash loop below treat the
+/// trailer as part of the message without actually concatenating it onto `bytes` (not possible in
+/// a `const fn`, which cannot allocate).
+const fn byte_at(bytes: &[u8], trailer: Option<u8>, i: usize) -> u8 {
+    if i < bytes.len() {
+        bytes[i]
+    } else {
+        match trailer {
+            Some(byte) => byte,
+            None => unreachable!(),
+        }
+    }
+}
+
+/// Computes the same SipHash-1-3 value (1 compression round, 3 finalization rounds, zero key
+/// `(0, 0)`) as [`std::collections::hash_map::DefaultHasher`], over `bytes` followed by `trailer`
+/// (if any). `str`'s `Hash` impl writes its bytes followed by a trailing `0xff` byte (to
+/// disambiguate a string from a byte slice of the same content), which is what `trailer` is for.
+const fn siphash13(bytes: &[u8], trailer: Option<u8>) -> u64 {
+    let mut v0: u64 = 0x736f6d6570736575;
+    let mut v1: u64 = 0x646f72616e646f6d;
+    let mut v2: u64 = 0x6c7967656e657261;
+    let mut v3: u64 = 0x7465646279746573;
+
+    let len = bytes.len() + if trailer.is_some() { 1 } else { 0 };
+    let num_blocks = len / 8;
+
+    let mut block = 0;
+    while block < num_blocks {
+        let offset = block * 8;
+        let m = u64::from_le_bytes([
+            byte_at(bytes, trailer, offset),
+            byte_at(bytes, trailer, offset + 1),
+            byte_at(bytes, trailer, offset + 2),
+            byte_at(bytes, trailer, offset + 3),
+            byte_at(bytes, trailer, offset + 4),
+            byte_at(bytes, trailer, offset + 5),
+            byte_at(bytes, trailer, offset + 6),
+            byte_at(bytes, trailer, offset + 7),
+        ]);
+
+        v3 ^= m;
+        let (a, b, c, d) = sipround(v0, v1, v2, v3);
+        v0 = a;
+        v1 = b;
+        v2 = c;
+        v3 = d;
+        v0 ^= m;
+
+        block += 1;
+    }
+
+    // Final partial block, zero-padded, with the (truncated to one byte) message length in the
+    // top byte.
+    let tail_start = num_blocks * 8;
+    let mut last_block = [0u8; 8];
+    let mut i = tail_start;
+    while i < len {
+        last_block[i - tail_start] = byte_at(bytes, trailer, i);
+        i += 1;
+    }
+    last_block[7] = len as u8;
+    let m = u64::from_le_bytes(last_block);
+
+    v3 ^= m;
+    let (a, b, c, d) = sipround(v0, v1, v2, v3);
+    v0 = a;
+    v1 = b;
+    v2 = c;
+    v3 = d;
+    v0 ^= m;
+
+    // Finalization: 3 more rounds, with `v2` perturbed to distinguish it from a compression round.
+    v2 ^= 0xff;
+    let (v0, v1, v2, v3) = sipround(v0, v1, v2, v3);
+    let (v0, v1, v2, v3) = sipround(v0, v1, v2, v3);
+    let (v0, v1, v2, v3) = sipround(v0, v1, v2, v3);
+
+    v0 ^ v1 ^ v2 ^ v3
+}
+
+/// Hashes the bytes to a `u64` the same way the `str_hash_default!` macro hashes its argument,
+/// i.e. matching what [`std::collections::hash_map::DefaultHasher`] (which, being keyed with fixed
+/// rather than random keys, hashes deterministically) computes for the `&str` of the same bytes.
+///
+/// ```
+/// assert_eq!(ministrhash_runtime::default_u64(b"foo"), ministrhash::str_hash_default!("foo"));
+/// ```
+pub const fn default_u64(bytes: &[u8]) -> u64 {
+    siphash13(bytes, Some(0xff))
+}
@@ -1,7 +1,11 @@
-//! Exports procedural macros for compile-time string literal hashing.
+//! Exports procedural macros for compile-time string literal hashing. See the companion
+//! `ministrhash-runtime` crate for matching `const fn` hashers usable on data that's only known at
+//! runtime (a `proc-macro` crate is restricted to exporting only macros, so those can't live here).
+//! `str_match!`'s generated dispatch code also calls into `ministrhash-runtime` at runtime, so
+//! using it requires that crate as a dependency too.
 
 use {
-    proc_macro::{Literal, TokenStream, TokenTree},
+    proc_macro::{Delimiter, Literal, TokenStream, TokenTree},
     std::{
         collections::hash_map::DefaultHasher,
         hash::{Hash, Hasher},
@@ -33,18 +37,286 @@ fn string_hash_default(string: &str) -> u64 {
 
 /// Hashes the string to a `u32` using FNV1a hash.
 fn string_hash_fnv1a(string: &str) -> u32 {
-    const FNV1A_PRIME: u32 = 0x0100_0193;
-    const FNV1A_SEED: u32 = 0x811C_9DC5;
+    byte_hash_fnv1a(string.as_bytes())
+}
+
+/// Hashes the bytes to a `u64` using the Rust's default hasher (i.e. one used in the `HashMap`).
+fn byte_hash_default(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+const FNV1A_32_SEED: u32 = 0x811C_9DC5;
+const FNV1A_32_PRIME: u32 = 0x0100_0193;
+
+const FNV1A_64_SEED: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV1A_64_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Hashes the bytes to a `u32` using FNV1a hash with the given `seed` and `prime`.
+fn byte_hash_fnv1a_32(bytes: &[u8], seed: u32, prime: u32) -> u32 {
+    let mut hash = seed;
+
+    for byte in bytes {
+        hash = (hash ^ *byte as u32).wrapping_mul(prime);
+    }
+
+    hash
+}
 
-    let mut hash = FNV1A_SEED;
+/// Hashes the bytes to a `u64` using FNV1a hash with the given `seed` and `prime`.
+fn byte_hash_fnv1a_64(bytes: &[u8], seed: u64, prime: u64) -> u64 {
+    let mut hash = seed;
 
-    for byte in string.as_bytes() {
-        hash = (hash ^ *byte as u32).wrapping_mul(FNV1A_PRIME);
+    for byte in bytes {
+        hash = (hash ^ *byte as u64).wrapping_mul(prime);
     }
 
     hash
 }
 
+/// Hashes the bytes to a `u32` using FNV1a hash with the default seed/prime.
+fn byte_hash_fnv1a(bytes: &[u8]) -> u32 {
+    byte_hash_fnv1a_32(bytes, FNV1A_32_SEED, FNV1A_32_PRIME)
+}
+
+/// Decodes the source representation of a quoted (non-raw) Rust string literal's contents
+/// (i.e. with the surrounding `"` already stripped), processing the escape sequences `rustc`
+/// itself understands: `\n \r \t \\ \" \' \0`, `\xNN` and `\u{...}`.
+fn unescape_str_literal(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    let mut chars = string.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next().expect("`string_hash` macro encountered an unterminated `\\` escape sequence") {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '\\' => result.push('\\'),
+            '"' => result.push('"'),
+            '\'' => result.push('\''),
+            '0' => result.push('\0'),
+            'x' => {
+                let hex: String = (&mut chars).take(2).collect();
+                assert!(hex.len() == 2, "`string_hash` macro encountered an incomplete `\\x` escape sequence");
+                let byte = u8::from_str_radix(&hex, 16).unwrap_or_else(|_| panic!("`string_hash` macro encountered an invalid `\\x{}` escape sequence", hex));
+                result.push(byte as char);
+            }
+            'u' => {
+                assert!(chars.next() == Some('{'), "`string_hash` macro expected a `{{` after a `\\u` escape");
+                let hex: String = (&mut chars).take_while(|&c| c != '}').collect();
+                let code_point = u32::from_str_radix(&hex, 16).unwrap_or_else(|_| panic!("`string_hash` macro encountered an invalid `\\u{{{}}}` escape sequence", hex));
+                result.push(char::from_u32(code_point).unwrap_or_else(|| panic!("`string_hash` macro encountered an invalid unicode scalar value `\\u{{{}}}`", hex)));
+            }
+            // String continuation: `\` immediately followed by a newline strips the newline and
+            // all following leading whitespace on the next line, same as `rustc`'s `STRING_CONTINUE`.
+            '\n' => {
+                while matches!(chars.clone().next(), Some(' ') | Some('\t') | Some('\n') | Some('\r')) {
+                    chars.next();
+                }
+            }
+            other => panic!("`string_hash` macro encountered an unsupported escape sequence `\\{}`", other),
+        }
+    }
+
+    result
+}
+
+/// Decodes the source representation of a quoted Rust string literal (e.g. `"a\nb"`, `r"a\"b"`,
+/// `r#"a"b"#`) into the string value it actually represents, so that hashing it produces the same
+/// result as hashing the real runtime `&str` value. Raw string literals are returned verbatim, with
+/// no escape processing, matching their defined semantics.
+fn decode_str_literal(string: &str) -> String {
+    // Raw string literal: `r"..."` / `r#"..."#` / `r##"..."##` / etc.
+    if let Some(rest) = string.strip_prefix('r') {
+        let num_hashes = rest.chars().take_while(|&c| c == '#').count();
+        let rest = &rest[num_hashes..];
+
+        assert!(rest.starts_with('"'), "`string_hash` macro takes one non-empty quoted string literal argument - `{}` is not a valid raw string literal", string);
+
+        let closing = format!("\"{}", "#".repeat(num_hashes));
+        assert!(rest.len() > closing.len() && rest.ends_with(closing.as_str()), "`string_hash` macro takes one non-empty quoted string literal argument - `{}` is not a valid raw string literal", string);
+
+        // Raw string contents are verbatim - no escape processing.
+        return rest[1..rest.len() - closing.len()].to_string();
+    }
+
+    assert!(string.len() >= 2, "`string_hash` macro takes one non-empty quoted string literal argument - `{}` was provided", string);
+    assert!(string.starts_with('"'), "`string_hash` macro takes one non-empty quoted string literal argument - `{}` does not start with a quote", string);
+    assert!(string.ends_with('"'), "`string_hash` macro takes one non-empty quoted string literal argument - `{}` does not end with a quote", string);
+
+    // Trim quotes: ["asdf"] -> [asdf].
+    unescape_str_literal(&string[1..string.len() - 1])
+}
+
+/// Decodes the source representation of a Rust byte string literal's contents (i.e. with the
+/// surrounding `b"..."` already stripped down to `"..."`), processing the escape sequences byte
+/// string literals understand: `\n \r \t \\ \" \' \0` and `\xNN` (unlike in non-byte string
+/// literals, `\xNN` may encode any byte value `0x00..=0xff`, and `\u{...}` is not supported).
+fn unescape_byte_str_literal(string: &str) -> Vec<u8> {
+    let mut result = Vec::with_capacity(string.len());
+    let mut chars = string.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            result.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        match chars.next().expect("`bytes_hash` macro encountered an unterminated `\\` escape sequence") {
+            'n' => result.push(b'\n'),
+            'r' => result.push(b'\r'),
+            't' => result.push(b'\t'),
+            '\\' => result.push(b'\\'),
+            '"' => result.push(b'"'),
+            '\'' => result.push(b'\''),
+            '0' => result.push(0u8),
+            'x' => {
+                let hex: String = (&mut chars).take(2).collect();
+                assert!(hex.len() == 2, "`bytes_hash` macro encountered an incomplete `\\x` escape sequence");
+                result.push(u8::from_str_radix(&hex, 16).unwrap_or_else(|_| panic!("`bytes_hash` macro encountered an invalid `\\x{}` escape sequence", hex)));
+            }
+            // String continuation: `\` immediately followed by a newline strips the newline and
+            // all following leading whitespace on the next line, same as `rustc`'s `STRING_CONTINUE`.
+            '\n' => {
+                while matches!(chars.clone().next(), Some(' ') | Some('\t') | Some('\n') | Some('\r')) {
+                    chars.next();
+                }
+            }
+            other => panic!("`bytes_hash` macro encountered an unsupported escape sequence `\\{}`", other),
+        }
+    }
+
+    result
+}
+
+/// Decodes the source representation of a Rust byte string literal (e.g. `b"a\nb"`, `br"a\"b"`,
+/// `br#"a"b"#`) into the bytes it actually represents, so that hashing it produces the same result
+/// as hashing the real runtime `&[u8]` value. Raw byte strings are returned verbatim, with no escape
+/// processing, matching their defined semantics.
+fn decode_byte_str_literal(literal: &str) -> Vec<u8> {
+    let string = literal.strip_prefix('b').unwrap_or_else(|| panic!("`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` does not start with `b`", literal));
+
+    // Raw byte string literal: `br"..."` / `br#"..."#` / `br##"..."##` / etc.
+    if let Some(rest) = string.strip_prefix('r') {
+        let num_hashes = rest.chars().take_while(|&c| c == '#').count();
+        let rest = &rest[num_hashes..];
+
+        assert!(rest.starts_with('"'), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` is not a valid raw byte string literal", literal);
+
+        let closing = format!("\"{}", "#".repeat(num_hashes));
+        assert!(rest.len() > closing.len() && rest.ends_with(closing.as_str()), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` is not a valid raw byte string literal", literal);
+
+        return rest.as_bytes()[1..rest.len() - closing.len()].to_vec();
+    }
+
+    assert!(string.len() >= 2, "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` was provided", literal);
+    assert!(string.starts_with('"'), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` does not start with a quote", literal);
+    assert!(string.ends_with('"'), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal argument - `{}` does not end with a quote", literal);
+
+    // Trim quotes: [b"asdf"] -> [asdf].
+    unescape_byte_str_literal(&string[1..string.len() - 1])
+}
+
+/// Strips `_` digit separators, as accepted anywhere in a Rust integer literal, out of `s`.
+fn strip_digit_separators(s: &str) -> String {
+    s.chars().filter(|&c| c != '_').collect()
+}
+
+/// Parses a single `u8` array element's literal source representation (e.g. `1`, `0xff`, `1u8`,
+/// `0b1111_0000`, `0o17`) into its byte value. `_` digit separators, as accepted anywhere in a Rust
+/// integer literal, are stripped before parsing.
+fn decode_byte_array_element(literal: &str) -> u8 {
+    const SUFFIXES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let trimmed = SUFFIXES
+        .iter()
+        .find_map(|suffix| literal.strip_suffix(suffix))
+        .unwrap_or(literal);
+    let trimmed = strip_digit_separators(trimmed);
+
+    if let Some(hex) = trimmed.strip_prefix("0x") {
+        u8::from_str_radix(hex, 16)
+    } else if let Some(bin) = trimmed.strip_prefix("0b") {
+        u8::from_str_radix(bin, 2)
+    } else if let Some(oct) = trimmed.strip_prefix("0o") {
+        u8::from_str_radix(oct, 8)
+    } else {
+        trimmed.parse()
+    }
+    .unwrap_or_else(|_| panic!("`bytes_hash` macro encountered an invalid `u8` array element `{}`", literal))
+}
+
+/// Parses a `[u8]` array literal's token stream (i.e. the contents of e.g. `[1, 2, 0xff]`) into
+/// the bytes it represents.
+fn decode_byte_array_literal(stream: TokenStream) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut iter = stream.into_iter();
+
+    while let Some(element) = iter.next() {
+        match element {
+            TokenTree::Literal(literal) => bytes.push(decode_byte_array_element(&literal.to_string())),
+            other => panic!("`bytes_hash` macro expected a `u8` array element, found `{}`", other),
+        }
+
+        match iter.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => continue,
+            Some(other) => panic!("`bytes_hash` macro expected a `,` between array elements, found `{}`", other),
+            None => break,
+        }
+    }
+
+    bytes
+}
+
+fn bytes_hash_impl<H: ToLiteral>(item: TokenStream, hash: fn(&[u8]) -> H) -> TokenStream {
+    let mut iter = item.into_iter();
+
+    let token = iter.next().expect("`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - none were provided");
+
+    let result: TokenStream;
+
+    match token {
+        TokenTree::Literal(literal) => {
+            let bytes = decode_byte_str_literal(&literal.to_string());
+            assert!(!bytes.is_empty(), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - an empty byte string was provided");
+
+            result = TokenStream::from(TokenTree::Literal(hash(&bytes).to_literal()));
+        }
+
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => {
+            let bytes = decode_byte_array_literal(group.stream());
+            assert!(!bytes.is_empty(), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - an empty byte array was provided");
+
+            result = TokenStream::from(TokenTree::Literal(hash(&bytes).to_literal()));
+        }
+
+        TokenTree::Group(group) => {
+            result = bytes_hash_impl(group.stream(), hash);
+        }
+
+        TokenTree::Ident(ident) => {
+            panic!("`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - ident `{}` was provided", ident);
+        }
+
+        TokenTree::Punct(punct) => {
+            panic!("`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - punct `{}` was provided", punct);
+        }
+    }
+
+    assert!(iter.next().is_none(), "`bytes_hash` macro takes one non-empty `b\"...\"` byte string literal (or `[u8]` array literal) argument - multiple were provided");
+
+    result
+}
+
 fn str_hash_impl<H: ToLiteral>(item: TokenStream, hash: fn(&str) -> H) -> TokenStream {
     let mut iter = item.into_iter();
 
@@ -54,18 +326,9 @@ fn str_hash_impl<H: ToLiteral>(item: TokenStream, hash: fn(&str) -> H) -> TokenS
 
     match string {
         TokenTree::Literal(string) => {
-            // At least [" "].
             let string = string.to_string();
-            assert!(string.len() >= 3, "`string_hash` macro takes one non-empty quoted string literal argument - `{}` was provided", string);
-
-            let first_char = &string[0..1];
-            assert!(first_char == "\"", "`string_hash` macro takes one non-empty quoted string literal argument - `{}` does not start with a quote", string);
-
-            let last_char = &string[string.len() - 1..string.len()];
-            assert!(last_char == "\"", "`string_hash` macro takes one non-empty quoted string literal argument - `{}` does not end with a quote", string);
-
-            // Trim quotes: ["asdf"] -> [asdf].
-            let string = &string[1..string.len() - 1];
+            let string = decode_str_literal(&string);
+            assert!(!string.is_empty(), "`string_hash` macro takes one non-empty quoted string literal argument - an empty string was provided");
 
             let hash_literal = hash(&string).to_literal();
 
@@ -97,7 +360,438 @@ pub fn str_hash_default(item: TokenStream) -> TokenStream {
 }
 
 /// Hashes the string to a `u32` using FNV1a hash.
+///
+/// ```
+/// // A `\` immediately followed by a newline is a string continuation - it and the following
+/// // line's leading whitespace are stripped, same as `rustc` itself evaluates the literal.
+/// assert_eq!(ministrhash::str_hash_fnv1a!("foo\
+///     bar"), ministrhash::str_hash_fnv1a!("foobar"));
+/// ```
 #[proc_macro]
 pub fn str_hash_fnv1a(item: TokenStream) -> TokenStream {
     str_hash_impl(item, string_hash_fnv1a)
 }
+
+/// Hashes the byte string (or `[u8]` array literal) to a `u64` using the Rust's default hasher
+/// (i.e. one used in the `HashMap`).
+#[proc_macro]
+pub fn bytes_hash_default(item: TokenStream) -> TokenStream {
+    bytes_hash_impl(item, byte_hash_default)
+}
+
+/// Hashes the byte string (or `[u8]` array literal) to a `u32` using FNV1a hash.
+///
+/// ```
+/// // `_` digit separators in a `[u8]` array element are accepted and don't truncate the value.
+/// assert_eq!(ministrhash::bytes_hash_fnv1a!([0x1_0, 0x2_5]), ministrhash::bytes_hash_fnv1a!([0x10, 0x25]));
+/// // `0b`/`0o` array elements are parsed too, not just `0x`.
+/// assert_eq!(ministrhash::bytes_hash_fnv1a!([0b1111_0000, 0o17]), ministrhash::bytes_hash_fnv1a!([0xf0, 0x0f]));
+/// ```
+#[proc_macro]
+pub fn bytes_hash_fnv1a(item: TokenStream) -> TokenStream {
+    bytes_hash_impl(item, byte_hash_fnv1a)
+}
+
+/// The result of a [`str_hash!`] invocation - the width of the produced hash depends on the chosen
+/// algorithm, so the literal it emits must be chosen at macro-expansion time rather than fixed by
+/// the (single, non-generic) return type of a `#[proc_macro]` function.
+enum HashValue {
+    U32(u32),
+    U64(u64),
+}
+
+impl ToLiteral for HashValue {
+    fn to_literal(&self) -> Literal {
+        match self {
+            HashValue::U32(hash) => hash.to_literal(),
+            HashValue::U64(hash) => hash.to_literal(),
+        }
+    }
+}
+
+/// Recursively unwraps a single token out of a transparent (`Delimiter::None`) group, which is how
+/// tokens forwarded through another macro's substitution may arrive wrapped, for macro hygiene.
+fn unwrap_transparent(token: TokenTree) -> TokenTree {
+    if let TokenTree::Group(group) = &token {
+        if group.delimiter() == Delimiter::None {
+            let mut inner = group.stream().into_iter();
+            if let (Some(only), None) = (inner.next(), inner.next()) {
+                return unwrap_transparent(only);
+            }
+        }
+    }
+
+    token
+}
+
+/// Parses an integer literal's source representation (e.g. `123`, `0x7B`, `123u64`, `0x1234_5678`)
+/// into a `u64`. `_` digit separators, as accepted anywhere in a Rust integer literal, are stripped
+/// before parsing.
+fn parse_int_literal(literal: &str) -> u64 {
+    if let Some(hex) = literal.strip_prefix("0x") {
+        let hex: String = strip_digit_separators(&hex.chars().take_while(|&c| c.is_ascii_hexdigit() || c == '_').collect::<String>());
+        u64::from_str_radix(&hex, 16)
+    } else {
+        let digits: String = strip_digit_separators(&literal.chars().take_while(|&c| c.is_ascii_digit() || c == '_').collect::<String>());
+        digits.parse()
+    }
+    .unwrap_or_else(|_| panic!("`str_hash!` macro encountered an invalid integer literal `{}`", literal))
+}
+
+/// Parses and consumes a single `,`-separated `name = <integer literal>` parameter (e.g.
+/// `seed = 0x1234`), if the next token is the identifier `name`. Returns the parsed value, or
+/// `None` if the next token isn't `name`.
+fn parse_uint_param(iter: &mut std::iter::Peekable<proc_macro::token_stream::IntoIter>, name: &str) -> Option<u64> {
+    match iter.peek() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == name => {}
+        _ => return None,
+    }
+    iter.next();
+
+    match iter.next().map(unwrap_transparent) {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+        other => panic!("`str_hash!` macro expected `=` after `{}`, found `{:?}`", name, other.map(|t| t.to_string())),
+    }
+
+    let value = match iter.next().map(unwrap_transparent) {
+        Some(TokenTree::Literal(literal)) => parse_int_literal(&literal.to_string()),
+        other => panic!("`str_hash!` macro expected an integer literal after `{} =`, found `{:?}`", name, other.map(|t| t.to_string())),
+    };
+
+    match iter.peek() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {
+            iter.next();
+        }
+        _ => {}
+    }
+
+    Some(value)
+}
+
+/// Hashes a quoted string literal using a macro-selected algorithm, with optional overrides of its
+/// default seed/prime constants, e.g. `str_hash!(fnv1a_64, "foo")` or
+/// `str_hash!(fnv1a_32, seed = 0x1234_5678, prime = 0x0100_0193, "foo")`.
+fn str_hash_generic_impl(item: TokenStream) -> TokenStream {
+    let mut iter = item.into_iter().peekable();
+
+    let algo = match iter.next().map(unwrap_transparent) {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        other => panic!("`str_hash!` macro expects an algorithm identifier as its first argument (one of `fnv1a_32`, `fnv1a_64`, `default`), found `{:?}`", other.map(|t| t.to_string())),
+    };
+
+    match iter.next().map(unwrap_transparent) {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+        other => panic!("`str_hash!` macro expected a `,` after the algorithm identifier, found `{:?}`", other.map(|t| t.to_string())),
+    }
+
+    let seed = parse_uint_param(&mut iter, "seed");
+    let prime = parse_uint_param(&mut iter, "prime");
+
+    let string = iter.next().map(unwrap_transparent).expect("`str_hash!` macro takes one non-empty quoted string literal argument - none were provided");
+
+    let string = match string {
+        TokenTree::Literal(literal) => decode_str_literal(&literal.to_string()),
+        other => panic!("`str_hash!` macro takes one non-empty quoted string literal argument, found `{}`", other),
+    };
+    assert!(!string.is_empty(), "`str_hash!` macro takes one non-empty quoted string literal argument - an empty string was provided");
+
+    assert!(iter.next().is_none(), "`str_hash!` macro takes one non-empty quoted string literal argument - multiple were provided");
+
+    let hash_value = match algo.as_str() {
+        "fnv1a_32" => HashValue::U32(byte_hash_fnv1a_32(
+            string.as_bytes(),
+            seed.map(|seed| u32::try_from(seed).unwrap_or_else(|_| panic!("`str_hash!` macro's `fnv1a_32` algorithm's `seed` value `{:#x}` does not fit in a `u32`", seed)))
+                .unwrap_or(FNV1A_32_SEED),
+            prime.map(|prime| u32::try_from(prime).unwrap_or_else(|_| panic!("`str_hash!` macro's `fnv1a_32` algorithm's `prime` value `{:#x}` does not fit in a `u32`", prime)))
+                .unwrap_or(FNV1A_32_PRIME),
+        )),
+        "fnv1a_64" => HashValue::U64(byte_hash_fnv1a_64(string.as_bytes(), seed.unwrap_or(FNV1A_64_SEED), prime.unwrap_or(FNV1A_64_PRIME))),
+        "default" => {
+            assert!(seed.is_none() && prime.is_none(), "`str_hash!` macro's `default` algorithm does not take `seed`/`prime` parameters");
+            HashValue::U64(string_hash_default(&string))
+        }
+        other => panic!("`str_hash!` macro does not support algorithm `{}` - expected one of `fnv1a_32`, `fnv1a_64`, `default`", other),
+    };
+
+    TokenStream::from(TokenTree::Literal(hash_value.to_literal()))
+}
+
+/// Hashes the quoted string literal using the chosen algorithm, with optional `seed`/`prime`
+/// overrides for the `fnv1a_32`/`fnv1a_64` algorithms, e.g. `str_hash!(fnv1a_64, "foo")` or
+/// `str_hash!(fnv1a_32, seed = 0x1234_5678, prime = 0x0100_0193, "foo")`. Supported algorithms are
+/// `fnv1a_32` (same as `str_hash_fnv1a!`), `fnv1a_64` (FNV1a with 64-bit seed `0xcbf29ce484222325`
+/// and prime `0x00000100000001B3`) and `default` (same as `str_hash_default!`, which does not take
+/// `seed`/`prime` parameters).
+///
+/// ```
+/// // `_` digit separators in a `seed`/`prime` literal are accepted and don't truncate the value.
+/// assert_eq!(
+///     ministrhash::str_hash!(fnv1a_32, seed = 0x1234_5678, "foo"),
+///     ministrhash::str_hash!(fnv1a_32, seed = 0x12345678, "foo"),
+/// );
+/// ```
+#[proc_macro]
+pub fn str_hash(item: TokenStream) -> TokenStream {
+    str_hash_generic_impl(item)
+}
+
+/// A single `"literal" => expr` (or `_ => expr`) arm parsed out of a [`str_match!`] invocation.
+enum MatchArm {
+    Key { key: String, body: TokenStream },
+    Default { body: TokenStream },
+}
+
+/// Splits `tokens` into segments separated by top-level `,` tokens - a comma nested inside a
+/// `TokenTree::Group` doesn't count, since the group is a single token as far as this iteration is
+/// concerned. Drops the single empty trailing segment left by an optional trailing comma.
+fn split_top_level_commas(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut segments = Vec::new();
+    let mut current = Vec::new();
+
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => segments.push(std::mem::take(&mut current)),
+            _ => current.push(token),
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push(current);
+    }
+
+    segments
+}
+
+/// Splits `tokens` at the first top-level `=>` into the pattern tokens before it and the body
+/// tokens after it, panicking if no `=>` is found.
+fn split_arm(tokens: Vec<TokenTree>) -> (Vec<TokenTree>, Vec<TokenTree>) {
+    for i in 0..tokens.len().saturating_sub(1) {
+        if let (TokenTree::Punct(eq), TokenTree::Punct(gt)) = (&tokens[i], &tokens[i + 1]) {
+            if eq.as_char() == '=' && gt.as_char() == '>' {
+                let mut pattern = tokens;
+                let body = pattern.split_off(i + 2);
+                pattern.truncate(i);
+                return (pattern, body);
+            }
+        }
+    }
+
+    panic!(
+        "`str_match!` macro expected a `\"literal\" => expr` or `_ => expr` arm, found `{}`",
+        tokens.into_iter().collect::<TokenStream>()
+    );
+}
+
+/// Parses one `,`-separated segment of a [`str_match!`] invocation (everything after the matched
+/// expression) into a [`MatchArm`].
+fn parse_match_arm(tokens: Vec<TokenTree>) -> MatchArm {
+    let (pattern, body) = split_arm(tokens);
+    assert!(!body.is_empty(), "`str_match!` macro expected a non-empty expression after `=>`");
+    let body = body.into_iter().collect::<TokenStream>();
+
+    match pattern.as_slice() {
+        [TokenTree::Ident(ident)] if ident.to_string() == "_" => MatchArm::Default { body },
+        [TokenTree::Literal(literal)] => MatchArm::Key { key: decode_str_literal(&literal.to_string()), body },
+        other => panic!(
+            "`str_match!` macro expected a quoted string literal or `_` before `=>`, found `{}`",
+            other.iter().cloned().collect::<TokenStream>()
+        ),
+    }
+}
+
+/// Runs the CHD (compress, hash, displace) minimal perfect hash construction over `keys`: buckets
+/// them by `fnv1a(key, seed = 0) mod m` (`m == keys.len()` buckets), then, largest bucket first,
+/// searches for the smallest displacement `d` such that `fnv1a(key, seed = d) mod n`
+/// (`n == keys.len()` slots) assigns every key in the bucket a distinct, still-free slot. Returns
+/// `(displacements, key_of_slot, key_slots)`, where `displacements[bucket]` is that bucket's
+/// resolved `d`, `key_of_slot[slot]` is the index into `keys` of the key assigned to `slot` (the
+/// hash lookup only narrows the candidate down to one slot - the generated code still compares the
+/// real key text stored there against the input, since a lone `fnv1a` hash match doesn't rule out a
+/// coincidental collision), and `key_slots[i]` is the slot `keys[i]` was assigned to.
+fn build_chd(keys: &[String]) -> (Vec<u32>, Vec<usize>, Vec<usize>) {
+    let n = keys.len();
+    let m = n;
+
+    let hash0: Vec<u32> = keys.iter().map(|key| byte_hash_fnv1a_32(key.as_bytes(), 0, FNV1A_32_PRIME)).collect();
+
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); m];
+    for (key_index, &hash) in hash0.iter().enumerate() {
+        buckets[(hash as usize) % m].push(key_index);
+    }
+
+    let mut bucket_order: Vec<usize> = (0..m).collect();
+    bucket_order.sort_by_key(|&bucket| std::cmp::Reverse(buckets[bucket].len()));
+
+    let mut displacements = vec![0u32; m];
+    let mut slot_of: Vec<Option<usize>> = vec![None; n];
+
+    for bucket in bucket_order {
+        let members = buckets[bucket].clone();
+        if members.is_empty() {
+            continue;
+        }
+
+        let mut resolved = false;
+
+        for d in 0u32..1_000_000 {
+            let mut candidate_slots = Vec::with_capacity(members.len());
+            let mut ok = true;
+
+            for &key_index in &members {
+                let slot = (byte_hash_fnv1a_32(keys[key_index].as_bytes(), d, FNV1A_32_PRIME) as usize) % n;
+
+                if slot_of[slot].is_some() || candidate_slots.contains(&slot) {
+                    ok = false;
+                    break;
+                }
+
+                candidate_slots.push(slot);
+            }
+
+            if ok {
+                for (&key_index, &slot) in members.iter().zip(candidate_slots.iter()) {
+                    slot_of[slot] = Some(key_index);
+                }
+                displacements[bucket] = d;
+                resolved = true;
+                break;
+            }
+        }
+
+        assert!(
+            resolved,
+            "`str_match!` macro could not resolve a perfect hash displacement for a bucket of {} keys after 1,000,000 attempts",
+            members.len()
+        );
+    }
+
+    let key_of_slot: Vec<usize> = slot_of
+        .into_iter()
+        .map(|slot| slot.expect("`str_match!` macro internal error - an unassigned slot remained after CHD construction"))
+        .collect();
+
+    let mut key_slots = vec![0usize; n];
+    for (slot, &key_index) in key_of_slot.iter().enumerate() {
+        key_slots[key_index] = slot;
+    }
+
+    (displacements, key_of_slot, key_slots)
+}
+
+/// Parses and expands a `str_match!(expr, "key" => expr, ..., _ => expr)` invocation into a
+/// collision-free CHD perfect-hash dispatch over the given keys.
+fn str_match_impl(item: TokenStream) -> TokenStream {
+    let mut segments = split_top_level_commas(item.into_iter().collect()).into_iter();
+
+    let input: TokenStream = segments
+        .next()
+        .expect("`str_match!` macro expects `expr, \"key\" => expr, ..., _ => expr` - no arguments were provided")
+        .into_iter()
+        .collect();
+    assert!(!input.is_empty(), "`str_match!` macro expects a non-empty expression to match as its first argument");
+
+    let mut keys = Vec::new();
+    let mut key_bodies = Vec::new();
+    let mut default_body = None;
+
+    for arm in segments.map(parse_match_arm) {
+        match arm {
+            MatchArm::Key { key, body } => {
+                keys.push(key);
+                key_bodies.push(body);
+            }
+            MatchArm::Default { body } => {
+                assert!(default_body.is_none(), "`str_match!` macro expects exactly one `_ => expr` default arm, found more than one");
+                default_body = Some(body);
+            }
+        }
+    }
+
+    assert!(!keys.is_empty(), "`str_match!` macro expects at least one `\"key\" => expr` arm");
+    let default_body = default_body.expect("`str_match!` macro expects exactly one `_ => expr` default arm, found none");
+
+    for i in 0..keys.len() {
+        for j in (i + 1)..keys.len() {
+            assert!(keys[i] != keys[j], "`str_match!` macro found a duplicate key `{}` (arms {} and {})", keys[i], i, j);
+        }
+    }
+
+    let (displacements, key_of_slot, key_slots) = build_chd(&keys);
+    let m = displacements.len();
+    let n = keys.len();
+
+    let displacements_literal = displacements.iter().map(u32::to_string).collect::<Vec<_>>().join(", ");
+    // `{:?}` on a `&str` produces a properly escaped, quoted Rust string literal - exactly what's
+    // needed to bake the real key text back into the generated source for the equality check below.
+    let keys_literal = key_of_slot.iter().map(|&key_index| format!("{:?}", keys[key_index])).collect::<Vec<_>>().join(", ");
+    let match_arms = key_bodies
+        .iter()
+        .zip(key_slots.iter())
+        .map(|(body, &slot)| format!("{} => {{ {} }},", slot, body))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let source = format!(
+        "{{
+            const __STR_MATCH_DISPLACEMENTS: [u32; {m}] = [{displacements_literal}];
+            const __STR_MATCH_KEYS: [&str; {n}] = [{keys_literal}];
+
+            let __str_match_input: &str = {input};
+            let __str_match_bytes = __str_match_input.as_bytes();
+            let __str_match_h0 = ::ministrhash_runtime::fnv1a_u32_seeded(__str_match_bytes, 0);
+            let __str_match_d = __STR_MATCH_DISPLACEMENTS[(__str_match_h0 as usize) % {m}];
+            let __str_match_slot = (::ministrhash_runtime::fnv1a_u32_seeded(__str_match_bytes, __str_match_d) as usize) % {n};
+
+            // The hash lookup narrows the input down to a single candidate slot, but `fnv1a` is not
+            // collision-resistant, so the real key text stored at that slot is compared against the
+            // input before committing to its arm - a hash-only match would otherwise risk silently
+            // dispatching to the wrong arm for an unrelated, hash-colliding input.
+            if __STR_MATCH_KEYS[__str_match_slot] == __str_match_input {{
+                match __str_match_slot {{
+                    {match_arms}
+                    _ => unreachable!(),
+                }}
+            }} else {{
+                {default_body}
+            }}
+        }}"
+    );
+
+    source
+        .parse()
+        .unwrap_or_else(|_| panic!("`str_match!` macro internal error - generated code failed to parse:\n{}", source))
+}
+
+/// Dispatches on the runtime `&str` value of `expr` against a fixed set of known string literal
+/// keys, e.g. `str_match!(input, "foo" => 1, "bar" => 2, _ => 0)`. Builds a collision-free CHD
+/// (compress, hash, displace) minimal perfect hash table over the keys at macro-expansion time:
+/// keys are bucketed by `fnv1a(key, seed = 0) mod m`, and each bucket is assigned, largest first,
+/// the smallest displacement `d` for which `fnv1a(key, seed = d) mod n` lands every key in the
+/// bucket on a still-free slot. The resulting `displacements`/`keys` tables are emitted as array
+/// literals, so the generated dispatch costs exactly two FNV1a hash evaluations and an array lookup
+/// to narrow the input down to a single candidate slot, followed by a string equality check against
+/// the real key text stored there (since `fnv1a` isn't collision-resistant, the hash alone can't be
+/// trusted to rule out an unrelated input) before the `match` on the resolved dense slot index runs;
+/// any input that isn't one of the known keys - including one that happens to collide on the hash -
+/// falls back to the `_` arm. A duplicate key, or a malformed arm, is a compile error. Requires the
+/// `ministrhash-runtime` crate as a dependency, since the generated lookup code calls its
+/// `fnv1a_u32_seeded` at runtime.
+///
+/// ```
+/// // "key479599" and "key662382" collide on `fnv1a(_, seed = 0)`, so this input resolves to the
+/// // same candidate slot as the "key479599" arm - it must still fall to `_`, not to that arm, since
+/// // the generated dispatch compares the actual key text, not just the hash.
+/// fn dispatch(input: &str) -> i32 {
+///     ministrhash::str_match!(input,
+///         "key479599" => 1,
+///         _ => 0,
+///     )
+/// }
+///
+/// assert_eq!(dispatch("key479599"), 1);
+/// assert_eq!(dispatch("key662382"), 0);
+/// ```
+#[proc_macro]
+pub fn str_match(item: TokenStream) -> TokenStream {
+    str_match_impl(item)
+}